@@ -9,56 +9,142 @@ async function getTauriCore() {
   return { invoke, listen }
 }
 
+// base64 chunks carry raw PTY bytes; xterm decodes a Uint8Array itself, so a
+// multi-byte character split across events is never mangled.
+function decodeChunk(body) {
+  const data = String(body?.data || '')
+  if (body?.encoding !== 'base64') return data
+  const binary = atob(data)
+  const bytes = new Uint8Array(binary.length)
+  for (let i = 0; i < binary.length; i += 1) {
+    bytes[i] = binary.charCodeAt(i)
+  }
+  return bytes
+}
+
+// Asked once per app load; base64 is preferred whenever the backend offers
+// it, and a backend that can't answer gets plain utf8.
+let protocolPromise = null
+
+function getProtocol() {
+  if (!protocolPromise) {
+    protocolPromise = getTauriCore()
+      .then(({ invoke }) => invoke('tauri_terminal_protocol'))
+      .catch(() => ({ version: null, encodings: ['utf8'] }))
+  }
+  return protocolPromise
+}
+
+async function negotiateEncoding() {
+  const encodings = (await getProtocol())?.encodings || []
+  if (encodings.includes('base64')) return 'base64'
+  return encodings.includes('utf8') ? 'utf8' : encodings[0] || 'utf8'
+}
+
+async function subscribe(sessionId, { onData = null, onExit = null }) {
+  const { listen } = await getTauriCore()
+  const [unlistenData, unlistenExit] = await Promise.all([
+    listen('terminal:pty-data', (event) => {
+      const body = event?.payload || {}
+      if (body?.session_id !== sessionId) return
+      if (typeof onData === 'function') {
+        onData(decodeChunk(body))
+      }
+    }),
+    listen('terminal:pty-exit', (event) => {
+      const body = event?.payload || {}
+      if (body?.session_id !== sessionId) return
+      if (typeof onExit === 'function') onExit(body)
+    }),
+  ])
+  return {
+    async dispose() {
+      unlistenData()
+      unlistenExit()
+    },
+  }
+}
+
 export const tauriTerminalService = {
   isTauriRuntime,
+  getProtocol,
 
   async startSession({ sessionId, cwd = null, cols = 120, rows = 32, onData = null, onExit = null }) {
     if (!isTauriRuntime()) {
       throw new Error('Tauri terminal is only available in desktop runtime.')
     }
 
-    const { invoke, listen } = await getTauriCore()
+    const { invoke } = await getTauriCore()
     const payload = {
       session_id: sessionId,
       sessionId,
       cwd,
       cols,
       rows,
+      encoding: await negotiateEncoding(),
     }
 
-    const [unlistenData, unlistenExit, response] = await Promise.all([
-      listen('terminal:pty-data', (event) => {
-        const body = event?.payload || {}
-        if (body?.session_id !== sessionId) return
-        if (typeof onData === 'function') {
-          onData(String(body?.data || ''))
-        }
-      }),
-      listen('terminal:pty-exit', (event) => {
-        const body = event?.payload || {}
-        if (body?.session_id !== sessionId) return
-        if (typeof onExit === 'function') onExit(body)
-      }),
+    const [subscription, response] = await Promise.all([
+      subscribe(sessionId, { onData, onExit }),
       invoke('tauri_terminal_start', payload),
     ])
 
     return {
       ...response,
-      async dispose() {
-        unlistenData()
-        unlistenExit()
-      },
+      dispose: subscription.dispose,
     }
   },
 
+  // Shells keep running across webview reloads; reattach replays their
+  // scrollback through onData before live output resumes.
+  async attachSession({ sessionId, cols = null, rows = null, onData = null, onExit = null }) {
+    if (!isTauriRuntime()) {
+      throw new Error('Tauri terminal is only available in desktop runtime.')
+    }
+
+    const { invoke } = await getTauriCore()
+    const subscription = await subscribe(sessionId, { onData, onExit })
+    try {
+      const response = await invoke('tauri_terminal_attach', {
+        session_id: sessionId,
+        sessionId,
+        cols,
+        rows,
+      })
+      return {
+        ...response,
+        dispose: subscription.dispose,
+      }
+    } catch (error) {
+      await subscription.dispose()
+      throw error
+    }
+  },
+
+  async listSessions() {
+    if (!isTauriRuntime()) return []
+    const { invoke } = await getTauriCore()
+    return invoke('tauri_terminal_list')
+  },
+
   async write(sessionId, data) {
     const { invoke } = await getTauriCore()
     return invoke('tauri_terminal_write', { session_id: sessionId, sessionId, data })
   },
 
-  async resize(sessionId, cols, rows) {
+  // Pixel size is optional; TUI programs use it to scale inline images.
+  async resize(sessionId, cols, rows, { pixelWidth = null, pixelHeight = null } = {}) {
     const { invoke } = await getTauriCore()
-    return invoke('tauri_terminal_resize', { session_id: sessionId, sessionId, cols, rows })
+    return invoke('tauri_terminal_resize', {
+      session_id: sessionId,
+      sessionId,
+      cols,
+      rows,
+      pixel_width: pixelWidth,
+      pixelWidth,
+      pixel_height: pixelHeight,
+      pixelHeight,
+    })
   },
 
   async stop(sessionId) {